@@ -15,6 +15,57 @@ pg_module_magic!();
 #[allow(non_camel_case_types)] // we don't want PascalCase in Postgres qualified names
 pub struct vectime(BTreeMap<String, i64>);
 
+/// Binary `send` function for `COPY ... WITH (FORMAT BINARY)` and the wire protocol. Encodes the
+/// inner `BTreeMap` as CBOR: its variable-length integers let small per-actor counters pack
+/// tightly while large ones still round-trip losslessly, and the map's existing key ordering
+/// gives a deterministic byte stream. Wired into the type via the `alter type` statement below -
+/// `#[derive(PostgresType)]` only generates the textual `in`/`out` functions, so the SEND/RECEIVE
+/// catalog entries have to be set explicitly.
+///
+/// `ALTER TYPE ... SET (send = ..., receive = ...)` for an existing base type is only valid on
+/// PostgreSQL 13+ (earlier versions can only set those at `CREATE TYPE` time, which
+/// `#[derive(PostgresType)]` doesn't give us a hook into) - so this whole binary-I/O wiring, and
+/// the `vectime_send`/`vectime_recv` functions themselves, are gated behind the `pg13` feature.
+/// On pg10/pg11/pg12 `vectime` has no binary representation and `COPY ... WITH (FORMAT BINARY)`
+/// falls back to text. `cargo pgx schema` emits SQL in source order, so the `alter type` statement
+/// must stay declared after both `vectime_send` and `vectime_recv` below - this version of
+/// `extension_sql!` has no `name`/`requires` dependency ordering of its own, it only checks that a
+/// trailing comma follows the SQL string.
+#[cfg(feature = "pg13")]
+#[pg_extern]
+pub fn vectime_send(timestamp: vectime) -> Vec<u8> {
+    let mut buf = Vec::new();
+    ciborium::ser::into_writer(&timestamp.0, &mut buf).expect("failed to encode vectime as CBOR");
+    buf
+}
+
+/// Binary `recv` function, the inverse of `vectime_send`.
+#[cfg(feature = "pg13")]
+#[pg_extern]
+pub fn vectime_recv(buf: StringInfo) -> vectime {
+    let map: BTreeMap<String, i64> = ciborium::de::from_reader(buf.as_bytes())
+        .expect("failed to decode vectime from CBOR");
+
+    // `ReceiveFunctionCall`/`CopyReadBinaryAttribute` require the `StringInfo` cursor to land
+    // exactly at the end of the message once we're done reading from it, or they raise "incorrect
+    // binary data format" on the assumption that leftover bytes mean we mis-decoded. The safe
+    // `StringInfo` wrapper has no cursor accessor, so advance the underlying struct's field
+    // directly - `ciborium` reads through a borrowed `&[u8]` and never touches this cursor itself.
+    let len = buf.len() as i32;
+    let sid: pgx::pg_sys::StringInfo = buf.into();
+    unsafe { (*sid).cursor = len; }
+
+    vectime(map)
+}
+
+#[cfg(feature = "pg13")]
+extension_sql!(r#"
+alter type vectime set (
+    send = vectime_send,
+    receive = vectime_recv
+);
+"#);
+
 /// Increment a partial counter at a given `id` by given `delta`. `delta` must not be less than 1.
 #[pg_extern]
 pub fn increment(mut vt: vectime, id: String, delta: i64) -> vectime {
@@ -48,6 +99,33 @@ create aggregate max (vectime) (
 );
 "#);
 
+/// Returns the pairwise minimum of two vector clocks - the greatest lower bound (meet) of the
+/// causal lattice. A key present in only one of the inputs is dropped, since an absent key counts
+/// as 0 and `min(v, 0) = 0`. Together with `max` this makes `vectime` a full bounded lattice.
+///
+/// Marked `strict` so the `min` aggregate's initial (NULL) state is replaced by the first row
+/// instead of being passed into this function - unlike `max`, `min` has no identity value to use
+/// as an `initcond`, since `min(v, '{}') = '{}'` for every `v`.
+#[pg_operator(strict, commutator)]
+#[opname(&&)]
+#[commutator(&&)]
+pub fn min(left: vectime, right: vectime) -> vectime {
+    let mut result = BTreeMap::new();
+    for (key, lv) in left.0.iter() {
+        if let Some(rv) = right.0.get(key) {
+            result.insert(key.clone(), (*lv).min(*rv));
+        }
+    }
+    vectime(result)
+}
+
+extension_sql!(r#"
+create aggregate min (vectime) (
+    sfunc = min,
+    stype = vectime
+);
+"#);
+
 /// Returns a sum of all values stored within vectime `timestamp`.
 /// Can be used to implement eg. Grow-only counter.
 #[pg_extern]
@@ -62,6 +140,46 @@ pub fn valueat(timestamp: vectime, key: String) -> i64 {
     *timestamp.0.get(&key).unwrap_or(&0)
 }
 
+/// Returns the actor ids of `timestamp`, in `BTreeMap` (sorted) order.
+#[pg_extern]
+pub fn keys(timestamp: vectime) -> Vec<String> {
+    timestamp.0.keys().cloned().collect()
+}
+
+/// Returns the partial counters of `timestamp`, in `BTreeMap` (sorted) order - aligned with the
+/// ids returned by `keys`.
+#[pg_extern]
+pub fn counters(timestamp: vectime) -> Vec<i64> {
+    timestamp.0.values().cloned().collect()
+}
+
+/// Decomposes `timestamp` into `(id, counter)` rows, in `BTreeMap` (sorted) order. Meant for
+/// `LATERAL` joins that need to pull a clock's internal structure into relational form.
+#[pg_extern]
+pub fn entries(timestamp: vectime) -> impl std::iter::Iterator<Item = (name!(id, String), name!(counter, i64))> {
+    timestamp.0.into_iter()
+}
+
+/// Builds a `vectime` out of parallel `ids`/`counters` arrays, the inverse of `keys`/`counters`.
+/// Unlike `increment`, which silently no-ops on a non-positive `delta`, this errors if the arrays
+/// differ in length or if any `counter` is not positive - there's no sensible partial result to
+/// fall back to when constructing a clock from scratch.
+#[pg_extern]
+pub fn vectime_from_arrays(ids: Vec<String>, counters: Vec<i64>) -> vectime {
+    if ids.len() != counters.len() {
+        error!("vectime_from_arrays: ids and counters must have the same length (got {} and {})", ids.len(), counters.len());
+    }
+
+    let mut map = BTreeMap::new();
+    for (id, counter) in ids.into_iter().zip(counters) {
+        if counter <= 0 {
+            error!("vectime_from_arrays: counter for '{}' must be greater than 0, got {}", id, counter);
+        }
+        map.insert(id, counter);
+    }
+    vectime(map)
+}
+
 
 impl PartialOrd for vectime {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -162,6 +280,68 @@ impl vectime {
         let varlena = pg_sys::pg_detoast_datum_packed(datum as *mut pg_sys::varlena);
         varsize_any_exhdr(varlena)
     }
+
+    unsafe fn payload_bytes(datum: Datum) -> &'static [u8] {
+        let varlena = pg_sys::pg_detoast_datum_packed(datum as *mut pg_sys::varlena);
+        let len = vectime::payload_len(datum);
+        let ptr = vardata_any(varlena) as *const u8;
+        std::slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// Marks the compressed on-disk form produced by `compress`, so `decompress` can tell it apart
+/// from an uncompressed entry written before this format existed.
+const COMPRESSED_MAGIC: &[u8; 4] = b"VTC1";
+
+/// Builds a new `GISTENTRY` carrying `key`, copying the `rel`/`page`/`offset`/`leafkey` bookkeeping
+/// fields from `source`. `compress`/`decompress` must return a `GISTENTRY*` Datum, not the raw key
+/// value Datum - returning the latter makes GiST read garbage `rel`/`page` fields out of the key's
+/// own varlena header.
+unsafe fn gistentry_with_key(source: &GISTENTRY, key: Datum) -> Datum {
+    let mut entry = PgBox::<GISTENTRY>::alloc();
+    entry.key = key;
+    entry.rel = source.rel;
+    entry.page = source.page;
+    entry.offset = source.offset;
+    entry.leafkey = source.leafkey;
+    entry.into_datum().unwrap()
+}
+
+fn zigzag_encode(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+fn zigzag_decode(v: u64) -> i64 {
+    ((v >> 1) as i64) ^ -((v & 1) as i64)
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        buf.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> u64 {
+    let mut result: u64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*pos];
+        *pos += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    result
 }
 
 /// Given an index entry p and a query value q, this function determines whether the index entry is
@@ -221,22 +401,77 @@ pub fn union(args: Internal<GistEntryVector>) -> vectime {
 }
 
 /// Converts the data item into a format suitable for physical storage in an index page.
+///
+/// Splits the actor-id keys from their counters: a sorted dictionary of the distinct keys is
+/// written once, followed by a parallel array of counters packed as delta/zigzag varints (the
+/// deltas between neighbouring counters tend to be small, so they pack tighter than the raw
+/// `i64`s). Each entry carries its own key dictionary; keys are not shared between the entries of
+/// a page. The output carries a
+/// magic prefix so `decompress` can tell it apart from entries written before this format existed.
 #[pg_extern]
 pub fn compress(entry: Internal<GISTENTRY>) -> Datum {
-    //TODO: at the moment we don't compress these, eventually vector time can be compressed by
-    // putting keys into separate space and leaving sequence numbers as an array
+    let value = match unsafe { vectime::from_datum(entry.0.key, false, 0) } {
+        Some(value) => value,
+        None => return entry.0.into_datum().unwrap(),
+    };
+
+    let mut buf = Vec::with_capacity(unsafe { vectime::payload_len(entry.0.key) });
+    buf.extend_from_slice(COMPRESSED_MAGIC);
+    write_varint(&mut buf, value.0.len() as u64);
+
+    for key in value.0.keys() {
+        let bytes = key.as_bytes();
+        write_varint(&mut buf, bytes.len() as u64);
+        buf.extend_from_slice(bytes);
+    }
+
+    let mut prev = 0i64;
+    for counter in value.0.values() {
+        write_varint(&mut buf, zigzag_encode(*counter - prev));
+        prev = *counter;
+    }
 
-    entry.0.into_datum().unwrap()
+    let key = buf.into_datum().unwrap();
+    unsafe { gistentry_with_key(&entry.0, key) }
 }
 
 /// The reverse of the `compress` method. Converts the index representation of the data item into
 /// a format that can be manipulated by the other GiST methods in the operator class.
+///
+/// Rebuilds the `BTreeMap` from the key dictionary and delta/zigzag-varint-packed counters when
+/// the `compress` magic prefix is present; entries written before this change lack the prefix and
+/// are passed through unchanged, so existing indexes keep working.
 #[pg_extern]
 pub fn decompress(entry: Internal<GISTENTRY>) -> Datum {
-    //TODO: at the moment we don't compress these, eventually vector time can be compressed by
-    // putting keys into separate space and leaving sequence numbers as an array
+    let bytes = unsafe { vectime::payload_bytes(entry.0.key) };
+
+    if bytes.len() < COMPRESSED_MAGIC.len() || &bytes[..COMPRESSED_MAGIC.len()] != COMPRESSED_MAGIC {
+        return entry.0.into_datum().unwrap();
+    }
+
+    let mut pos = COMPRESSED_MAGIC.len();
+    let n = read_varint(bytes, &mut pos) as usize;
+
+    let mut ids = Vec::with_capacity(n);
+    for _ in 0..n {
+        let len = read_varint(bytes, &mut pos) as usize;
+        let id = std::str::from_utf8(&bytes[pos..pos + len])
+            .expect("vectime compressed entry contains invalid utf-8 key")
+            .to_string();
+        pos += len;
+        ids.push(id);
+    }
+
+    let mut map = BTreeMap::new();
+    let mut prev = 0i64;
+    for id in ids {
+        let counter = prev + zigzag_decode(read_varint(bytes, &mut pos));
+        map.insert(id, counter);
+        prev = counter;
+    }
 
-    entry.0.into_datum().unwrap()
+    let key = vectime(map).into_datum().unwrap();
+    unsafe { gistentry_with_key(&entry.0, key) }
 }
 
 /// Returns a value indicating the “cost” of inserting the new entry into a particular branch of the
@@ -258,14 +493,133 @@ pub fn penalty(origin: Internal<GISTENTRY>, new_entry: Internal<GISTENTRY>, mut
     *p
 }
 
+/// Mandatory GiST support function 7 ("equal"): compares two stored keys for equality and reports
+/// the result through the `result` out-param, the same shape `penalty` above uses for its own
+/// out-param. `initGISTstate` fetches this proc unconditionally - without it, `CREATE INDEX ...
+/// USING gist (v)` fails outright with "missing support function 7 for attribute 1 of index".
+/// It's a distinct catalog entry from the `~=` operator's `same` function below, which is why it
+/// needs its own Rust name.
+#[pg_extern]
+pub fn gist_same(left: Datum, right: Datum, mut result: Internal<bool>) -> bool {
+    let l = unsafe { vectime::from_datum(left, false, 0) };
+    let r = unsafe { vectime::from_datum(right, false, 0) };
+    let eq = l == r;
+    *result.0.deref_mut() = eq;
+    eq
+}
+
+fn picksplit_cost(v: &vectime) -> (i64, i64) {
+    (v.0.len() as i64, valueof(v.clone()))
+}
+
+fn picksplit_cost_delta(a: (i64, i64), b: (i64, i64)) -> (i64, i64) {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+/// Groups `values` into two non-empty groups using Guttman's quadratic split algorithm. The cost
+/// of a `vectime` union is the number of distinct keys it carries (timestamps sharing actor keys
+/// are causally related and should stay together), with ties broken by `valueof`. The two entries
+/// whose merged cost exceeds the sum of their individual costs by the widest margin become the
+/// seeds of the left/right groups; every remaining entry is then assigned to whichever group's
+/// union grows the least by absorbing it, ties going to the smaller group to keep the split
+/// balanced. Returns the 0-based indexes into `values` for the left and right groups.
+///
+/// Panics if `values` has fewer than two entries - a split always needs two non-empty groups.
+fn quadratic_split(values: &[vectime]) -> (Vec<usize>, Vec<usize>) {
+    let n = values.len();
+    assert!(n >= 2, "quadratic_split needs at least two entries, got {}", n);
+
+    // seed selection: the pair whose union costs the most relative to the seeds themselves
+    let mut seed_left = 0usize;
+    let mut seed_right = 1usize;
+    let mut best_d = (i64::MIN, i64::MIN);
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let union = max(values[i].clone(), values[j].clone());
+            let d = picksplit_cost_delta(
+                picksplit_cost_delta(picksplit_cost(&union), picksplit_cost(&values[i])),
+                picksplit_cost(&values[j]),
+            );
+            if d > best_d {
+                best_d = d;
+                seed_left = i;
+                seed_right = j;
+            }
+        }
+    }
+
+    let mut left_idx = vec![seed_left];
+    let mut right_idx = vec![seed_right];
+    let mut left_union = values[seed_left].clone();
+    let mut right_union = values[seed_right].clone();
+
+    for i in 0..n {
+        if i == seed_left || i == seed_right {
+            continue;
+        }
+        let e = &values[i];
+        let g_left = picksplit_cost_delta(picksplit_cost(&max(left_union.clone(), e.clone())), picksplit_cost(&left_union));
+        let g_right = picksplit_cost_delta(picksplit_cost(&max(right_union.clone(), e.clone())), picksplit_cost(&right_union));
+
+        // ties are broken towards the smaller group, to keep the split balanced
+        let goes_left = match g_left.cmp(&g_right) {
+            Ordering::Less => true,
+            Ordering::Greater => false,
+            Ordering::Equal => left_idx.len() <= right_idx.len(),
+        };
+
+        if goes_left {
+            left_union = max(left_union, e.clone());
+            left_idx.push(i);
+        } else {
+            right_union = max(right_union, e.clone());
+            right_idx.push(i);
+        }
+    }
+
+    (left_idx, right_idx)
+}
+
 /// When an index page split is necessary, this function decides which entries on the page are to
-/// stay on the old page, and which are to move to the new page.
+/// stay on the old page, and which are to move to the new page. See `quadratic_split` for the
+/// grouping algorithm.
 #[pg_extern]
 pub fn picksplit(entry: Internal<GistEntryVector>, mut split: Internal<GIST_SPLITVEC>) -> Datum {
-    let v = unsafe { entry.0.vector.as_slice(entry.0.n as usize) };
+    let n = entry.0.n as usize;
+    let v = unsafe { entry.0.vector.as_slice(n) };
+
+    // Postgres sets `n = maxoff + 1` and leaves `vector[0]` unused; the real entries live at
+    // `vector[1..=maxoff]`, offsets `FirstOffsetNumber..=maxoff`. `union`'s vector, by contrast,
+    // is 0-based - don't copy its iteration pattern here.
+    let values: Vec<vectime> = v[1..n].iter()
+        .map(|e| unsafe { vectime::from_datum(e.key, false, 0).unwrap_or_default() })
+        .collect();
 
+    let (left_idx, right_idx) = quadratic_split(&values);
+    let left_union = left_idx.iter().fold(vectime::default(), |acc, &i| max(acc, values[i].clone()));
+    let right_union = right_idx.iter().fold(vectime::default(), |acc, &i| max(acc, values[i].clone()));
+
+    unsafe {
+        let spl_left = pg_sys::palloc0(left_idx.len() * std::mem::size_of::<pg_sys::OffsetNumber>()) as *mut pg_sys::OffsetNumber;
+        let spl_right = pg_sys::palloc0(right_idx.len() * std::mem::size_of::<pg_sys::OffsetNumber>()) as *mut pg_sys::OffsetNumber;
 
-    unimplemented!()
+        for (slot, idx) in left_idx.iter().enumerate() {
+            *spl_left.add(slot) = (*idx as pg_sys::OffsetNumber) + pg_sys::FirstOffsetNumber;
+        }
+        for (slot, idx) in right_idx.iter().enumerate() {
+            *spl_right.add(slot) = (*idx as pg_sys::OffsetNumber) + pg_sys::FirstOffsetNumber;
+        }
+
+        let s = split.0.deref_mut();
+        s.spl_left = spl_left;
+        s.spl_nleft = left_idx.len() as i32;
+        s.spl_right = spl_right;
+        s.spl_nright = right_idx.len() as i32;
+        s.spl_ldatum = left_union.into_datum().unwrap();
+        s.spl_rdatum = right_union.into_datum().unwrap();
+    }
+
+    split.0.into_datum().unwrap()
 }
 
 /* End of support for GiST Index */
@@ -298,16 +652,23 @@ pub fn contained(t1: vectime, t2: vectime) -> bool {
 extension_sql!(r#"
 create operator class vectime_ops
     default for type vectime using gist as
-        function    8   contained(vectime, vectime),
-        function    7   contains(vectime, vectime),
-        function    6   same(vectime, vectime),
-        function    3   intersects(vectime, vectime);
+        operator    3   ?#(vectime, vectime),
+        operator    6   ~=(vectime, vectime),
+        operator    7   @>(vectime, vectime),
+        operator    8   <@(vectime, vectime),
+        function    1   consistent(internal, vectime, smallint, oid, internal),
+        function    2   union(internal),
+        function    3   compress(internal),
+        function    4   decompress(internal),
+        function    5   penalty(internal, internal, internal),
+        function    6   picksplit(internal, internal),
+        function    7   gist_same(internal, internal, internal);
 "#);
 
 #[cfg(test)]
 mod test {
     use std::cmp::Ordering;
-    use crate::{vectime, increment, max};
+    use crate::{vectime, increment, max, min, keys, counters, vectime_from_arrays, zigzag_encode, zigzag_decode, write_varint, read_varint, quadratic_split};
 
     fn vtime(a: i64, b:i64, c: i64) -> vectime {
         let ts = vectime::default();
@@ -347,4 +708,142 @@ mod test {
         assert_max(vtime(1, 3, 3), vtime(1, 2, 4), vtime(1, 3, 4));
         assert_max(vtime(1, 0, 1), vtime(1, 1, 0), vtime(1, 1, 1));
     }
+
+    #[test]
+    fn vtime_min() {
+
+        fn assert_min(left: vectime, right: vectime, expected: vectime) {
+            assert_eq!(min(left, right), expected);
+        }
+
+        assert_min(vtime(0, 0, 0), vtime(0, 0, 0), vtime(0, 0, 0));
+        assert_min(vtime(2, 2, 3), vtime(1, 2, 0), vtime(1, 2, 0));
+        assert_min(vtime(1, 3, 3), vtime(1, 2, 4), vtime(1, 2, 3));
+        assert_min(vtime(1, 0, 1), vtime(1, 1, 0), vtime(1, 0, 0));
+    }
+
+    #[test]
+    fn vtime_keys_values_roundtrip() {
+        let ts = vtime(1, 2, 3);
+        let ids = keys(ts.clone());
+        let vals = counters(ts.clone());
+
+        assert_eq!(ids, vec!["A".to_string(), "B".to_string(), "C".to_string()]);
+        assert_eq!(vals, vec![1, 2, 3]);
+        assert_eq!(vectime_from_arrays(ids, vals), ts);
+    }
+
+    #[test]
+    fn varint_zigzag_roundtrip() {
+        for value in vec![0i64, 1, -1, 42, -42, i64::MAX, i64::MIN] {
+            assert_eq!(zigzag_decode(zigzag_encode(value)), value);
+        }
+
+        let mut buf = Vec::new();
+        let values = vec![0u64, 1, 127, 128, 300, u64::MAX];
+        for value in &values {
+            write_varint(&mut buf, *value);
+        }
+
+        let mut pos = 0;
+        for value in &values {
+            assert_eq!(read_varint(&buf, &mut pos), *value);
+        }
+        assert_eq!(pos, buf.len());
+    }
+
+    #[test]
+    fn quadratic_split_groups_every_entry_exactly_once() {
+        let entries = vec![
+            vtime(1, 0, 0),
+            vtime(0, 1, 0),
+            vtime(0, 0, 1),
+            vtime(1, 1, 0),
+            vtime(1, 1, 1),
+        ];
+
+        let (left, right) = quadratic_split(&entries);
+
+        assert!(!left.is_empty());
+        assert!(!right.is_empty());
+
+        let mut assigned: Vec<usize> = left.iter().chain(right.iter()).cloned().collect();
+        assigned.sort();
+        assert_eq!(assigned, (0..entries.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn quadratic_split_requires_at_least_two_entries() {
+        let entries = vec![vtime(1, 0, 0)];
+        quadratic_split(&entries);
+    }
+}
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pg_schema]
+mod pg_test {
+    use pgx::*;
+    use crate::{vectime, valueat, vectime_from_arrays};
+
+    /// `vectime_from_arrays` goes through pgx's `error!`, which reports through Postgres's own
+    /// error machinery rather than a plain Rust `panic!`, so it needs a backend to run against.
+    #[pg_test(error = "vectime_from_arrays: ids and counters must have the same length (got 2 and 1)")]
+    fn vectime_from_arrays_rejects_mismatched_lengths() {
+        vectime_from_arrays(vec!["A".to_string(), "B".to_string()], vec![1]);
+    }
+
+    #[pg_test(error = "vectime_from_arrays: counter for 'A' must be greater than 0, got 0")]
+    fn vectime_from_arrays_rejects_non_positive_counter() {
+        vectime_from_arrays(vec!["A".to_string()], vec![0]);
+    }
+
+    /// Exercises the actual `COPY ... WITH (FORMAT BINARY)` path, to make sure `vectime_send`/
+    /// `vectime_recv` are really reached via the catalog wiring rather than just being callable
+    /// Rust functions. Only registered on pg13+, since that's where `vectime_send`/`vectime_recv`
+    /// are wired in (see the `pg13` feature gate on those functions).
+    #[cfg(feature = "pg13")]
+    #[pg_test]
+    fn vectime_binary_copy_roundtrip() {
+        Spi::run("create temp table vectime_binary_test (v vectime)");
+        Spi::run("insert into vectime_binary_test values (increment(increment('{}'::vectime, 'A', 1), 'B', 2))");
+        Spi::run("copy vectime_binary_test to '/tmp/vectime_binary_test.bin' with (format binary)");
+        Spi::run("truncate vectime_binary_test");
+        Spi::run("copy vectime_binary_test from '/tmp/vectime_binary_test.bin' with (format binary)");
+
+        let roundtripped = Spi::get_one::<vectime>("select v from vectime_binary_test")
+            .expect("expected a row after the binary COPY roundtrip");
+
+        assert_eq!(valueat(roundtripped.clone(), "A".to_string()), 1);
+        assert_eq!(valueat(roundtripped, "B".to_string()), 2);
+    }
+
+    /// Drives inserts and a query through an actual `vectime_ops` GiST index, so that `compress`
+    /// and `decompress` are exercised on their real (non-passthrough) path rather than just being
+    /// callable Rust functions.
+    #[pg_test]
+    fn vectime_gist_index_insert_and_query() {
+        Spi::run("create temp table vectime_gist_test (v vectime)");
+        Spi::run("create index on vectime_gist_test using gist (v)");
+        Spi::run(
+            "insert into vectime_gist_test \
+             select increment(increment('{}'::vectime, 'A', i), 'B', i) \
+             from generate_series(1, 200) as i",
+        );
+
+        let count = Spi::get_one::<i64>(
+            "select count(*) from vectime_gist_test where v @> '{\"A\": 10, \"B\": 10}'::vectime",
+        ).expect("expected a count");
+
+        assert_eq!(count, 190);
+    }
+}
+
+#[cfg(test)]
+pub mod pg_test_harness {
+    pub fn setup(_options: Vec<&str>) {}
+
+    pub fn postgresql_conf_options() -> Vec<&'static str> {
+        vec![]
+    }
 }
\ No newline at end of file